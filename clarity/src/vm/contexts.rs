@@ -1830,6 +1830,13 @@ impl ContractContext {
             || self.defined_traits.contains_key(name)
     }
 
+    /// Names of the data maps defined by this contract, in sorted order.
+    pub fn list_maps(&self) -> Vec<ClarityName> {
+        let mut map_names: Vec<_> = self.meta_data_map.keys().cloned().collect();
+        map_names.sort();
+        map_names
+    }
+
     pub fn get_clarity_version(&self) -> &ClarityVersion {
         &self.clarity_version
     }
@@ -14,14 +14,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::convert::TryInto;
 
 use stacks_common::types::StacksEpochId;
 
-use crate::vm::ast::ContractAST;
+use crate::vm::ast::{build_ast_with_rules, ASTRules, ContractAST};
 use crate::vm::callables::CallableType;
 use crate::vm::contexts::{ContractContext, Environment, GlobalContext, LocalContext};
-use crate::vm::errors::InterpreterResult as Result;
+use crate::vm::costs::LimitedCostTracker;
+use crate::vm::errors::{CheckErrors, InterpreterResult as Result};
 use crate::vm::representations::SymbolicExpression;
 use crate::vm::types::{PrincipalData, QualifiedContractIdentifier};
 use crate::vm::version::ClarityVersion;
@@ -58,3 +60,113 @@ impl Contract {
         self.contract_context.canonicalize_types(epoch);
     }
 }
+
+/// Collect every contract that `exprs` depends on at deployment: the target of each
+///  `contract-call?` and the contracts of the traits named by `use-trait` and `impl-trait`.
+///  Other contract principals (e.g., `(is-eq contract-caller .other)`) are only data.
+fn collect_contract_references(
+    exprs: &[SymbolicExpression],
+    references: &mut BTreeSet<QualifiedContractIdentifier>,
+) {
+    for expr in exprs.iter() {
+        let list = match expr.match_list() {
+            Some(list) => list,
+            None => continue,
+        };
+        match list
+            .first()
+            .and_then(|head| head.match_atom())
+            .map(|name| name.as_str())
+        {
+            Some("contract-call?") => {
+                if let Some(Value::Principal(PrincipalData::Contract(contract_identifier))) =
+                    list.get(1).and_then(|target| target.match_literal_value())
+                {
+                    references.insert(contract_identifier.clone());
+                }
+            }
+            Some("use-trait") | Some("impl-trait") => {
+                for trait_identifier in list.iter().filter_map(|arg| arg.match_field()) {
+                    references.insert(trait_identifier.contract_identifier.clone());
+                }
+            }
+            _ => {}
+        }
+        collect_contract_references(list, references);
+    }
+}
+
+/// Order `contracts` so that each contract is deployed after every other contract in the
+///  set that it references. Contracts outside of the set are assumed to already be deployed.
+///  The result is a depth-first post-order: contracts are visited in input order, and each
+///  one is emitted right after its dependencies, which may move a dependency ahead of
+///  contracts listed before it.
+///
+/// Returns `CheckErrors::CircularReference` naming the contracts in the cycle if the
+///  references are circular.
+pub fn resolve_deployment_order(
+    contracts: &[(QualifiedContractIdentifier, &str)],
+    clarity_version: ClarityVersion,
+    epoch: StacksEpochId,
+) -> Result<Vec<QualifiedContractIdentifier>> {
+    let mut dependencies = HashMap::new();
+    for (contract_identifier, source) in contracts.iter() {
+        let ast = build_ast_with_rules(
+            contract_identifier,
+            source,
+            &mut LimitedCostTracker::new_free(),
+            clarity_version,
+            epoch,
+            ASTRules::PrecheckSize,
+        )?;
+        let mut references = BTreeSet::new();
+        collect_contract_references(&ast.expressions, &mut references);
+        references.remove(contract_identifier);
+        dependencies.insert(contract_identifier.clone(), references);
+    }
+
+    // depth-first search with an explicit stack, so that long dependency chains don't
+    //  grow the call stack. `path` is the chain of contracts currently being visited, each
+    //  with the references it has left to visit; `on_path` holds the same contracts and is
+    //  used to detect cycles.
+    let mut finished = HashSet::new();
+    let mut order = Vec::with_capacity(contracts.len());
+    for (root, _) in contracts.iter() {
+        if finished.contains(root) {
+            continue;
+        }
+        let mut path = vec![(root, dependencies[root].iter())];
+        let mut on_path = HashSet::new();
+        on_path.insert(root);
+
+        while let Some((_, remaining)) = path.last_mut() {
+            match remaining.next() {
+                Some(reference) => {
+                    if finished.contains(reference) {
+                        continue;
+                    }
+                    if on_path.contains(reference) {
+                        let start = path.iter().position(|(c, _)| *c == reference).unwrap_or(0);
+                        let cycle = path[start..].iter().map(|(c, _)| c.to_string()).collect();
+                        return Err(CheckErrors::CircularReference(cycle).into());
+                    }
+                    let references = match dependencies.get(reference) {
+                        Some(references) => references,
+                        // not part of this deployment
+                        None => continue,
+                    };
+                    on_path.insert(reference);
+                    path.push((reference, references.iter()));
+                }
+                None => {
+                    if let Some((contract_identifier, _)) = path.pop() {
+                        on_path.remove(contract_identifier);
+                        finished.insert(contract_identifier.clone());
+                        order.push(contract_identifier.clone());
+                    }
+                }
+            }
+        }
+    }
+    Ok(order)
+}
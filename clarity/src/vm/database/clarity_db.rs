@@ -1510,6 +1510,31 @@ impl<'a> ClarityDatabase<'a> {
         }
     }
 
+    /// Returns whether or not the data map holds an entry for `key_value`.
+    ///  Deleted entries are stored as `none`, and are reported as absent. The stored
+    ///  value is compared in its serialized form, so it is never deserialized.
+    pub fn contains_entry(
+        &mut self,
+        contract_identifier: &QualifiedContractIdentifier,
+        map_name: &str,
+        key_value: &Value,
+        map_descriptor: &DataMapMetadata,
+    ) -> Result<bool> {
+        Self::check_map_key(
+            map_descriptor,
+            &self.get_clarity_epoch_version()?,
+            key_value,
+        )?;
+
+        let key =
+            ClarityDatabase::make_key_for_data_map_entry(contract_identifier, map_name, key_value)?;
+
+        match self.get::<String>(&key)? {
+            None => Ok(false),
+            Some(stored_hex) => Ok(stored_hex != Value::none().serialize_to_hex()?),
+        }
+    }
+
     pub fn set_entry(
         &mut self,
         contract_identifier: &QualifiedContractIdentifier,
@@ -1613,23 +1638,34 @@ impl<'a> ClarityDatabase<'a> {
         map_descriptor: &DataMapMetadata,
         epoch: &StacksEpochId,
     ) -> Result<ValueResult> {
-        if !map_descriptor
-            .key_type
-            .admits(&self.get_clarity_epoch_version()?, &key_value)?
-        {
-            return Err(
-                CheckErrors::TypeValueError(map_descriptor.key_type.clone(), key_value).into(),
-            );
-        }
-        if !map_descriptor
-            .value_type
-            .admits(&self.get_clarity_epoch_version()?, &value)?
-        {
-            return Err(
-                CheckErrors::TypeValueError(map_descriptor.value_type.clone(), value).into(),
-            );
-        }
+        let clarity_epoch = self.get_clarity_epoch_version()?;
+        Self::check_map_key(map_descriptor, &clarity_epoch, &key_value)?;
+        Self::check_map_value(map_descriptor, &clarity_epoch, &value)?;
+
+        self.inner_set_entry_unchecked(
+            contract_identifier,
+            map_name,
+            key_value,
+            value,
+            return_if_exists,
+            map_descriptor,
+            epoch,
+        )
+    }
 
+    /// Write a data map entry whose key and value have already been checked
+    ///  against `map_descriptor`.
+    #[allow(clippy::too_many_arguments)]
+    fn inner_set_entry_unchecked(
+        &mut self,
+        contract_identifier: &QualifiedContractIdentifier,
+        map_name: &str,
+        key_value: Value,
+        value: Value,
+        return_if_exists: bool,
+        map_descriptor: &DataMapMetadata,
+        epoch: &StacksEpochId,
+    ) -> Result<ValueResult> {
         let key_serialized = key_value.serialize_to_hex()?;
         let key_serialized_byte_len = byte_len_of_serialization(&key_serialized);
         let key = ClarityDatabase::make_key_for_quad(
@@ -1668,17 +1704,27 @@ impl<'a> ClarityDatabase<'a> {
         map_descriptor: &DataMapMetadata,
         epoch: &StacksEpochId,
     ) -> Result<ValueResult> {
-        if !map_descriptor
-            .key_type
-            .admits(&self.get_clarity_epoch_version()?, key_value)?
-        {
-            return Err(CheckErrors::TypeValueError(
-                map_descriptor.key_type.clone(),
-                (*key_value).clone(),
-            )
-            .into());
-        }
+        let clarity_epoch = self.get_clarity_epoch_version()?;
+        Self::check_map_key(map_descriptor, &clarity_epoch, key_value)?;
 
+        self.delete_entry_unchecked(
+            contract_identifier,
+            map_name,
+            key_value,
+            map_descriptor,
+            epoch,
+        )
+    }
+
+    /// Delete a data map entry whose key has already been checked against `map_descriptor`.
+    fn delete_entry_unchecked(
+        &mut self,
+        contract_identifier: &QualifiedContractIdentifier,
+        map_name: &str,
+        key_value: &Value,
+        map_descriptor: &DataMapMetadata,
+        epoch: &StacksEpochId,
+    ) -> Result<ValueResult> {
         let key_serialized = key_value.serialize_to_hex()?;
         let key_serialized_byte_len = byte_len_of_serialization(&key_serialized);
         let key = ClarityDatabase::make_key_for_quad(
@@ -1706,6 +1752,252 @@ impl<'a> ClarityDatabase<'a> {
                 })?,
         })
     }
+
+    /// Deletes the entry for `key_value`, returning the removed value as
+    ///  a Clarity optional (`none` if nothing was stored).
+    pub fn remove_entry(
+        &mut self,
+        contract_identifier: &QualifiedContractIdentifier,
+        map_name: &str,
+        key_value: &Value,
+        map_descriptor: &DataMapMetadata,
+        epoch: &StacksEpochId,
+    ) -> Result<Value> {
+        Self::check_map_key(
+            map_descriptor,
+            &self.get_clarity_epoch_version()?,
+            key_value,
+        )?;
+
+        let key =
+            ClarityDatabase::make_key_for_data_map_entry(contract_identifier, map_name, key_value)?;
+        let stored_type = TypeSignature::new_option(map_descriptor.value_type.clone())?;
+        let removed = match self.get_value(&key, &stored_type, epoch)? {
+            None => return Ok(Value::none()),
+            Some(data) => data.value,
+        };
+
+        if removed != Value::none() {
+            self.put_value(&key, Value::none(), epoch)?;
+        }
+        Ok(removed)
+    }
+
+    /// Sets the entry for `key_value` to `value`, returning the replaced value as
+    ///  a Clarity optional (`none` if nothing was stored). Unlike `set_entry`, this reads the
+    ///  existing entry first, so it is only meant for host callers: `map-set` keeps its costs.
+    pub fn replace_entry(
+        &mut self,
+        contract_identifier: &QualifiedContractIdentifier,
+        map_name: &str,
+        key_value: Value,
+        value: Value,
+        map_descriptor: &DataMapMetadata,
+        epoch: &StacksEpochId,
+    ) -> Result<Value> {
+        let clarity_epoch = self.get_clarity_epoch_version()?;
+        Self::check_map_key(map_descriptor, &clarity_epoch, &key_value)?;
+        Self::check_map_value(map_descriptor, &clarity_epoch, &value)?;
+
+        let key = ClarityDatabase::make_key_for_data_map_entry(
+            contract_identifier,
+            map_name,
+            &key_value,
+        )?;
+        let stored_type = TypeSignature::new_option(map_descriptor.value_type.clone())?;
+        let replaced = match self.get_value(&key, &stored_type, epoch)? {
+            None => Value::none(),
+            Some(data) => data.value,
+        };
+
+        self.inner_set_entry_unchecked(
+            contract_identifier,
+            map_name,
+            key_value,
+            value,
+            false,
+            map_descriptor,
+            epoch,
+        )?;
+        Ok(replaced)
+    }
+
+    fn check_map_key(
+        map_descriptor: &DataMapMetadata,
+        clarity_epoch: &StacksEpochId,
+        key_value: &Value,
+    ) -> Result<()> {
+        if !map_descriptor.key_type.admits(clarity_epoch, key_value)? {
+            return Err(CheckErrors::TypeValueError(
+                map_descriptor.key_type.clone(),
+                key_value.clone(),
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    fn check_map_value(
+        map_descriptor: &DataMapMetadata,
+        clarity_epoch: &StacksEpochId,
+        value: &Value,
+    ) -> Result<()> {
+        if !map_descriptor.value_type.admits(clarity_epoch, value)? {
+            return Err(CheckErrors::TypeValueError(
+                map_descriptor.value_type.clone(),
+                value.clone(),
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Check a batch of keys and values against the map's types with `admits_slice`. The
+    ///  first rejected entry is checked again on its own to build the error, so a batch fails
+    ///  with the same error as the first `set_entry` call that would fail.
+    /// On failure, returns the index of that entry along with the error.
+    fn check_entry_types(
+        map_descriptor: &DataMapMetadata,
+        clarity_epoch: &StacksEpochId,
+        keys: &[Value],
+        values: &[Value],
+    ) -> std::result::Result<(), (usize, Error)> {
+        let rejected = [
+            map_descriptor.key_type.admits_slice(clarity_epoch, keys),
+            map_descriptor
+                .value_type
+                .admits_slice(clarity_epoch, values),
+        ]
+        .into_iter()
+        .filter_map(|checked| checked.err())
+        .min();
+        if let Some(index) = rejected {
+            Self::check_map_key(map_descriptor, clarity_epoch, &keys[index])
+                .and_then(|_| Self::check_map_value(map_descriptor, clarity_epoch, &values[index]))
+                .map_err(|e| (index, e))?;
+        }
+        Ok(())
+    }
+
+    /// Insert a batch of entries into a data map. Every key and value is checked against the
+    ///  map's types before anything is written, and the writes are applied in a nested layer,
+    ///  so either every entry is processed or the map is left untouched.
+    /// Returns, for each entry, whether or not it was inserted.
+    pub fn insert_entries(
+        &mut self,
+        contract_identifier: &QualifiedContractIdentifier,
+        map_name: &str,
+        entries: Vec<(Value, Value)>,
+        map_descriptor: &DataMapMetadata,
+        epoch: &StacksEpochId,
+    ) -> Result<Vec<bool>> {
+        let (keys, values): (Vec<Value>, Vec<Value>) = entries.into_iter().unzip();
+        let clarity_epoch = self.get_clarity_epoch_version()?;
+        Self::check_entry_types(map_descriptor, &clarity_epoch, &keys, &values)
+            .map_err(|(_, e)| e)?;
+
+        self.begin();
+        let mut inserted = Vec::with_capacity(keys.len());
+        for (key_value, value) in keys.into_iter().zip(values) {
+            match self.inner_set_entry_unchecked(
+                contract_identifier,
+                map_name,
+                key_value,
+                value,
+                true,
+                map_descriptor,
+                epoch,
+            ) {
+                Ok(result) => inserted.push(result.value == Value::Bool(true)),
+                Err(e) => {
+                    self.roll_back()?;
+                    return Err(e);
+                }
+            }
+        }
+        self.commit()?;
+
+        Ok(inserted)
+    }
+
+    /// Set a batch of entries in a data map. Like `insert_entries`, every key and value is
+    ///  checked before anything is written, and the writes are applied in a nested layer.
+    /// If an entry is rejected, nothing is written and the inner result holds the index of
+    ///  the first rejected entry along with the TypeValueError `set_entry` would return.
+    ///  The outer result fails only if the database itself does.
+    pub fn set_entries(
+        &mut self,
+        contract_identifier: &QualifiedContractIdentifier,
+        map_name: &str,
+        entries: Vec<(Value, Value)>,
+        map_descriptor: &DataMapMetadata,
+        epoch: &StacksEpochId,
+    ) -> Result<std::result::Result<(), (usize, Error)>> {
+        let (keys, values): (Vec<Value>, Vec<Value>) = entries.into_iter().unzip();
+        let clarity_epoch = self.get_clarity_epoch_version()?;
+        if let Err(rejected) =
+            Self::check_entry_types(map_descriptor, &clarity_epoch, &keys, &values)
+        {
+            return Ok(Err(rejected));
+        }
+
+        self.begin();
+        for (key_value, value) in keys.into_iter().zip(values) {
+            if let Err(e) = self.inner_set_entry_unchecked(
+                contract_identifier,
+                map_name,
+                key_value,
+                value,
+                false,
+                map_descriptor,
+                epoch,
+            ) {
+                self.roll_back()?;
+                return Err(e);
+            }
+        }
+        self.commit()?;
+
+        Ok(Ok(()))
+    }
+
+    /// Delete a batch of keys from a data map. Every key is checked against the map's key
+    ///  type before anything is deleted, and the deletes are applied in a nested layer.
+    /// Returns, for each key, whether or not an entry was deleted.
+    pub fn delete_entries(
+        &mut self,
+        contract_identifier: &QualifiedContractIdentifier,
+        map_name: &str,
+        keys: Vec<Value>,
+        map_descriptor: &DataMapMetadata,
+        epoch: &StacksEpochId,
+    ) -> Result<Vec<bool>> {
+        let clarity_epoch = self.get_clarity_epoch_version()?;
+        if let Err(index) = map_descriptor.key_type.admits_slice(&clarity_epoch, &keys) {
+            Self::check_map_key(map_descriptor, &clarity_epoch, &keys[index])?;
+        }
+
+        self.begin();
+        let mut deleted = Vec::with_capacity(keys.len());
+        for key_value in keys.iter() {
+            match self.delete_entry_unchecked(
+                contract_identifier,
+                map_name,
+                key_value,
+                map_descriptor,
+                epoch,
+            ) {
+                Ok(result) => deleted.push(result.value == Value::Bool(true)),
+                Err(e) => {
+                    self.roll_back()?;
+                    return Err(e);
+                }
+            }
+        }
+        self.commit()?;
+
+        Ok(deleted)
+    }
 }
 
 // Asset Functions
@@ -22,6 +22,7 @@ use stacks_common::types::StacksEpochId;
 use crate::vm::ast::errors::ParseErrors;
 use crate::vm::ast::ASTRules;
 use crate::vm::contexts::Environment;
+use crate::vm::contracts::resolve_deployment_order;
 use crate::vm::errors::{CheckErrors, Error, RuntimeErrorType};
 use crate::vm::tests::{
     env_factory, execute, is_committed, is_err_code_i128 as is_err_code, symbols_from_values,
@@ -1150,3 +1151,91 @@ fn test_cc_trait_stack_depth(
         RuntimeErrorType::MaxStackDepthReached.into()
     );
 }
+
+#[test]
+fn test_resolve_deployment_order() {
+    let traits =
+        "(define-trait token-trait ((transfer? (principal principal uint) (response bool uint))))";
+    let token = "(impl-trait .traits.token-trait)
+         (define-public (transfer? (from principal) (to principal) (amount uint)) (ok true))";
+    let app = "(use-trait token-trait .traits.token-trait)
+         (define-public (pay (amount uint))
+           (contract-call? .token transfer? tx-sender .app amount))";
+    let standalone = "(define-read-only (me) .standalone)";
+
+    let id = |name: &str| QualifiedContractIdentifier::local(name).unwrap();
+    let contracts = [
+        (id("app"), app),
+        (id("token"), token),
+        (id("standalone"), standalone),
+        (id("traits"), traits),
+    ];
+
+    assert_eq!(
+        resolve_deployment_order(
+            &contracts,
+            ClarityVersion::Clarity2,
+            StacksEpochId::latest()
+        )
+        .unwrap(),
+        vec![id("traits"), id("token"), id("app"), id("standalone")]
+    );
+}
+
+#[test]
+fn test_resolve_deployment_order_cycle() {
+    let id = |name: &str| QualifiedContractIdentifier::local(name).unwrap();
+    let contracts = [
+        (id("d"), "(define-read-only (get-d) u4)"),
+        (
+            id("a"),
+            "(define-public (call-b) (contract-call? .b call-c))",
+        ),
+        (
+            id("b"),
+            "(define-public (call-c) (contract-call? .c call-a))",
+        ),
+        (
+            id("c"),
+            "(define-public (call-a) (contract-call? .a call-b))",
+        ),
+    ];
+
+    assert_eq!(
+        resolve_deployment_order(
+            &contracts,
+            ClarityVersion::Clarity2,
+            StacksEpochId::latest()
+        )
+        .unwrap_err(),
+        CheckErrors::CircularReference(vec![
+            id("a").to_string(),
+            id("b").to_string(),
+            id("c").to_string(),
+        ])
+        .into()
+    );
+}
+
+#[test]
+fn test_resolve_deployment_order_caller_check() {
+    // `.a` only compares `contract-caller` against `.b`, so it does not depend on `.b`
+    let id = |name: &str| QualifiedContractIdentifier::local(name).unwrap();
+    let contracts = [
+        (id("b"), "(define-public (call-a) (contract-call? .a f))"),
+        (
+            id("a"),
+            "(define-public (f) (begin (asserts! (is-eq contract-caller .b) (err u1)) (ok true)))",
+        ),
+    ];
+
+    assert_eq!(
+        resolve_deployment_order(
+            &contracts,
+            ClarityVersion::Clarity2,
+            StacksEpochId::latest()
+        )
+        .unwrap(),
+        vec![id("a"), id("b")]
+    );
+}
@@ -16,12 +16,25 @@
 
 use std::convert::{From, TryFrom};
 
+#[cfg(any(test, feature = "testing"))]
+use rstest::rstest;
+use stacks_common::types::StacksEpochId;
+
+use crate::vm::ast::ASTRules;
+use crate::vm::database::ClarityDatabase;
 use crate::vm::errors::{CheckErrors, Error, ShortReturnType};
+use crate::vm::tests::{test_epochs, tl_env_factory, TopLevelMemoryEnvironmentGenerator};
 use crate::vm::types::{
-    ListData, SequenceData, TupleData, TupleTypeSignature, TypeSignature, Value,
+    ListData, QualifiedContractIdentifier, SequenceData, TupleData, TupleTypeSignature,
+    TypeSignature, Value,
 };
 use crate::vm::{execute, ClarityName};
 
+const KV_STORE_CONTRACT: &str = "(define-map kv-store { key: int } { value: int })
+         (map-insert kv-store { key: 1 } { value: 10 })
+         (map-insert kv-store { key: 2 } { value: 20 })
+         (map-delete kv-store { key: 2 })";
+
 fn assert_executes(expected: Result<Value, Error>, input: &str) {
     assert_eq!(expected.unwrap(), execute(input).unwrap().unwrap());
 }
@@ -465,11 +478,24 @@ fn silly_naming_system() {
     assert_executes(expected, test1);
 }
 
+#[test]
+fn test_tuple_key_field_order() {
+    let test = "(define-map pairs { a: int, b: int } { sum: int })
+         (map-insert pairs { b: 2, a: 1 } { sum: 3 })
+         (and (is-eq (map-get? pairs { a: 1, b: 2 }) (some { sum: 3 }))
+              (is-eq (map-get? pairs (tuple (b 2) (a 1))) (some { sum: 3 }))
+              (not (map-insert pairs { a: 1, b: 2 } { sum: 4 })))";
+
+    assert_executes(Ok(Value::Bool(true)), test);
+}
+
 #[test]
 fn datamap_errors() {
     let tests = [
         "(map-get? non-existent (tuple (name 1)))",
         "(map-delete non-existent (tuple (name 1)))",
+        "(map-insert non-existent (tuple (name 1)) (tuple (value 1)))",
+        "(map-set non-existent (tuple (name 1)) (tuple (value 1)))",
     ];
 
     for program in tests.iter() {
@@ -478,6 +504,14 @@ fn datamap_errors() {
             CheckErrors::NoSuchMap("non-existent".to_string()).into()
         );
     }
+
+    // map names are matched exactly, so a name differing only in case is undefined
+    let program = "(define-map existent { name: int } { value: int })
+         (map-get? Existent (tuple (name 1)))";
+    assert_eq!(
+        execute(program).unwrap_err(),
+        CheckErrors::NoSuchMap("Existent".to_string()).into()
+    );
 }
 
 #[test]
@@ -815,3 +849,517 @@ fn test_non_tuple_map_kv_store() {
     let expected = Value::list_from(vec![Value::Int(0)]);
     assert_executes(expected, &test_get);
 }
+
+fn kv_key(key: i128) -> Value {
+    Value::from(TupleData::from_data(vec![("key".into(), Value::Int(key))]).unwrap())
+}
+
+fn kv_value(value: i128) -> Value {
+    Value::from(TupleData::from_data(vec![("value".into(), Value::Int(value))]).unwrap())
+}
+
+/// Deploy `KV_STORE_CONTRACT` and run `test` against the resulting database.
+fn with_kv_store_db<F>(
+    epoch: StacksEpochId,
+    tl_env_factory: &mut TopLevelMemoryEnvironmentGenerator,
+    test: F,
+) where
+    F: FnOnce(&mut ClarityDatabase, &QualifiedContractIdentifier),
+{
+    with_contract_db(epoch, tl_env_factory, "kv-store", KV_STORE_CONTRACT, test)
+}
+
+/// Deploy `contract` as `.<contract_name>` and run `test` against the resulting database.
+fn with_contract_db<F>(
+    epoch: StacksEpochId,
+    tl_env_factory: &mut TopLevelMemoryEnvironmentGenerator,
+    contract_name: &str,
+    contract: &str,
+    test: F,
+) where
+    F: FnOnce(&mut ClarityDatabase, &QualifiedContractIdentifier),
+{
+    let contract_id = QualifiedContractIdentifier::local(contract_name).unwrap();
+    let mut owned_env = tl_env_factory.get_env(epoch);
+    owned_env
+        .initialize_contract(contract_id.clone(), contract, None, ASTRules::PrecheckSize)
+        .unwrap();
+
+    let (mut db, _) = owned_env.destruct().unwrap();
+    db.begin();
+    test(&mut db, &contract_id);
+    db.roll_back().unwrap();
+}
+
+#[apply(test_epochs)]
+fn test_contains_entry(
+    epoch: StacksEpochId,
+    mut tl_env_factory: TopLevelMemoryEnvironmentGenerator,
+) {
+    with_kv_store_db(epoch, &mut tl_env_factory, |db, contract_id| {
+        let descriptor = db.load_map(contract_id, "kv-store").unwrap();
+
+        assert!(db
+            .contains_entry(contract_id, "kv-store", &kv_key(1), &descriptor)
+            .unwrap());
+        // deleted entries are not reported as present
+        assert!(!db
+            .contains_entry(contract_id, "kv-store", &kv_key(2), &descriptor)
+            .unwrap());
+        assert!(!db
+            .contains_entry(contract_id, "kv-store", &kv_key(3), &descriptor)
+            .unwrap());
+
+        assert_eq!(
+            db.contains_entry(contract_id, "kv-store", &Value::Int(1), &descriptor)
+                .unwrap_err(),
+            CheckErrors::TypeValueError(descriptor.key_type.clone(), Value::Int(1)).into()
+        );
+    });
+}
+
+#[apply(test_epochs)]
+fn test_contains_entry_stored_none(
+    epoch: StacksEpochId,
+    mut tl_env_factory: TopLevelMemoryEnvironmentGenerator,
+) {
+    let contract = "(define-map flags { id: int } (optional int))
+         (map-insert flags { id: 1 } none)";
+    with_contract_db(
+        epoch,
+        &mut tl_env_factory,
+        "flags",
+        contract,
+        |db, contract_id| {
+            let descriptor = db.load_map(contract_id, "flags").unwrap();
+            let key =
+                Value::from(TupleData::from_data(vec![("id".into(), Value::Int(1))]).unwrap());
+
+            // a stored `none` value is `(some none)`, which is distinct from a missing entry
+            assert!(db
+                .contains_entry(contract_id, "flags", &key, &descriptor)
+                .unwrap());
+            assert_eq!(
+                db.fetch_entry(contract_id, "flags", &key, &descriptor, &epoch)
+                    .unwrap(),
+                Value::some(Value::none()).unwrap()
+            );
+
+            db.delete_entry(contract_id, "flags", &key, &descriptor, &epoch)
+                .unwrap();
+            assert!(!db
+                .contains_entry(contract_id, "flags", &key, &descriptor)
+                .unwrap());
+        },
+    );
+}
+
+#[apply(test_epochs)]
+fn test_insert_entry_after_roll_back(
+    epoch: StacksEpochId,
+    mut tl_env_factory: TopLevelMemoryEnvironmentGenerator,
+) {
+    with_kv_store_db(epoch, &mut tl_env_factory, |db, contract_id| {
+        let descriptor = db.load_map(contract_id, "kv-store").unwrap();
+
+        db.begin();
+        let inserted = db
+            .insert_entry(
+                contract_id,
+                "kv-store",
+                kv_key(3),
+                kv_value(30),
+                &descriptor,
+                &epoch,
+            )
+            .unwrap();
+        assert_eq!(inserted.value, Value::Bool(true));
+        db.roll_back().unwrap();
+
+        // the aborted insert must not be visible, so inserting the same key succeeds again
+        let inserted = db
+            .insert_entry(
+                contract_id,
+                "kv-store",
+                kv_key(3),
+                kv_value(31),
+                &descriptor,
+                &epoch,
+            )
+            .unwrap();
+        assert_eq!(inserted.value, Value::Bool(true));
+        assert_eq!(
+            db.fetch_entry(contract_id, "kv-store", &kv_key(3), &descriptor, &epoch)
+                .unwrap(),
+            Value::some(kv_value(31)).unwrap()
+        );
+    });
+}
+
+#[apply(test_epochs)]
+fn test_insert_and_delete_entries(
+    epoch: StacksEpochId,
+    mut tl_env_factory: TopLevelMemoryEnvironmentGenerator,
+) {
+    with_kv_store_db(epoch, &mut tl_env_factory, |db, contract_id| {
+        let descriptor = db.load_map(contract_id, "kv-store").unwrap();
+
+        // empty batches are a no-op
+        assert_eq!(
+            db.insert_entries(contract_id, "kv-store", vec![], &descriptor, &epoch)
+                .unwrap(),
+            Vec::<bool>::new()
+        );
+        assert_eq!(
+            db.delete_entries(contract_id, "kv-store", vec![], &descriptor, &epoch)
+                .unwrap(),
+            Vec::<bool>::new()
+        );
+
+        // a single ill-typed entry rejects the whole batch before anything is written
+        let err = db
+            .insert_entries(
+                contract_id,
+                "kv-store",
+                vec![(kv_key(4), kv_value(40)), (kv_key(5), Value::Int(50))],
+                &descriptor,
+                &epoch,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            CheckErrors::TypeValueError(descriptor.value_type.clone(), Value::Int(50)).into()
+        );
+        assert!(!db
+            .contains_entry(contract_id, "kv-store", &kv_key(4), &descriptor)
+            .unwrap());
+
+        assert_eq!(
+            db.insert_entries(
+                contract_id,
+                "kv-store",
+                vec![
+                    (kv_key(1), kv_value(11)),
+                    (kv_key(4), kv_value(40)),
+                    (kv_key(5), kv_value(50)),
+                ],
+                &descriptor,
+                &epoch,
+            )
+            .unwrap(),
+            vec![false, true, true]
+        );
+        assert_eq!(
+            db.fetch_entry(contract_id, "kv-store", &kv_key(1), &descriptor, &epoch)
+                .unwrap(),
+            Value::some(kv_value(10)).unwrap()
+        );
+
+        let err = db
+            .delete_entries(
+                contract_id,
+                "kv-store",
+                vec![kv_key(4), Value::Int(5)],
+                &descriptor,
+                &epoch,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            CheckErrors::TypeValueError(descriptor.key_type.clone(), Value::Int(5)).into()
+        );
+        assert!(db
+            .contains_entry(contract_id, "kv-store", &kv_key(4), &descriptor)
+            .unwrap());
+
+        assert_eq!(
+            db.delete_entries(
+                contract_id,
+                "kv-store",
+                vec![kv_key(4), kv_key(6)],
+                &descriptor,
+                &epoch,
+            )
+            .unwrap(),
+            vec![true, false]
+        );
+        assert!(!db
+            .contains_entry(contract_id, "kv-store", &kv_key(4), &descriptor)
+            .unwrap());
+    });
+}
+
+#[apply(test_epochs)]
+fn test_nested_roll_back_keeps_outer_writes(
+    epoch: StacksEpochId,
+    mut tl_env_factory: TopLevelMemoryEnvironmentGenerator,
+) {
+    with_kv_store_db(epoch, &mut tl_env_factory, |db, contract_id| {
+        let descriptor = db.load_map(contract_id, "kv-store").unwrap();
+
+        db.begin();
+        db.set_entry(
+            contract_id,
+            "kv-store",
+            kv_key(10),
+            kv_value(1),
+            &descriptor,
+            &epoch,
+        )
+        .unwrap();
+
+        db.begin();
+        db.set_entry(
+            contract_id,
+            "kv-store",
+            kv_key(11),
+            kv_value(2),
+            &descriptor,
+            &epoch,
+        )
+        .unwrap();
+
+        db.begin();
+        db.set_entry(
+            contract_id,
+            "kv-store",
+            kv_key(12),
+            kv_value(3),
+            &descriptor,
+            &epoch,
+        )
+        .unwrap();
+        // overwriting in the innermost layer must not clobber the middle layer's write
+        db.set_entry(
+            contract_id,
+            "kv-store",
+            kv_key(11),
+            kv_value(4),
+            &descriptor,
+            &epoch,
+        )
+        .unwrap();
+        db.roll_back().unwrap();
+
+        db.commit().unwrap();
+        db.commit().unwrap();
+
+        assert_eq!(
+            db.fetch_entry(contract_id, "kv-store", &kv_key(10), &descriptor, &epoch)
+                .unwrap(),
+            Value::some(kv_value(1)).unwrap()
+        );
+        assert_eq!(
+            db.fetch_entry(contract_id, "kv-store", &kv_key(11), &descriptor, &epoch)
+                .unwrap(),
+            Value::some(kv_value(2)).unwrap()
+        );
+        assert_eq!(
+            db.fetch_entry(contract_id, "kv-store", &kv_key(12), &descriptor, &epoch)
+                .unwrap(),
+            Value::none()
+        );
+    });
+}
+
+#[apply(test_epochs)]
+fn test_list_maps(epoch: StacksEpochId, mut tl_env_factory: TopLevelMemoryEnvironmentGenerator) {
+    let contract = "(define-map owners uint principal)
+         (define-data-var counter int 0)
+         (define-map balances principal uint)
+         (define-map allowances { owner: principal, spender: principal } uint)";
+    with_contract_db(
+        epoch,
+        &mut tl_env_factory,
+        "token",
+        contract,
+        |db, contract_id| {
+            let contract = db.get_contract(contract_id).unwrap();
+            let expected: Vec<ClarityName> =
+                vec!["allowances".into(), "balances".into(), "owners".into()];
+            assert_eq!(contract.contract_context.list_maps(), expected);
+        },
+    );
+}
+
+#[apply(test_epochs)]
+fn test_set_entries(epoch: StacksEpochId, mut tl_env_factory: TopLevelMemoryEnvironmentGenerator) {
+    with_kv_store_db(epoch, &mut tl_env_factory, |db, contract_id| {
+        let descriptor = db.load_map(contract_id, "kv-store").unwrap();
+
+        let rejected = db
+            .set_entries(
+                contract_id,
+                "kv-store",
+                vec![(kv_key(1), kv_value(11)), (Value::Int(7), kv_value(70))],
+                &descriptor,
+                &epoch,
+            )
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            rejected,
+            (
+                1,
+                CheckErrors::TypeValueError(descriptor.key_type.clone(), Value::Int(7)).into()
+            )
+        );
+        assert_eq!(
+            db.fetch_entry(contract_id, "kv-store", &kv_key(1), &descriptor, &epoch)
+                .unwrap(),
+            Value::some(kv_value(10)).unwrap()
+        );
+
+        db.set_entries(
+            contract_id,
+            "kv-store",
+            vec![(kv_key(1), kv_value(11)), (kv_key(7), kv_value(70))],
+            &descriptor,
+            &epoch,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            db.fetch_entry(contract_id, "kv-store", &kv_key(1), &descriptor, &epoch)
+                .unwrap(),
+            Value::some(kv_value(11)).unwrap()
+        );
+        assert_eq!(
+            db.fetch_entry(contract_id, "kv-store", &kv_key(7), &descriptor, &epoch)
+                .unwrap(),
+            Value::some(kv_value(70)).unwrap()
+        );
+    });
+}
+
+#[apply(test_epochs)]
+fn test_remove_entry(epoch: StacksEpochId, mut tl_env_factory: TopLevelMemoryEnvironmentGenerator) {
+    let contract = "(define-map escrows { id: uint } { amount: uint, terms: { expiry: uint, memo: (buff 4) } })
+         (map-insert escrows { id: u1 } { amount: u100, terms: { expiry: u50, memo: 0x01020304 } })";
+    with_contract_db(
+        epoch,
+        &mut tl_env_factory,
+        "escrows",
+        contract,
+        |db, contract_id| {
+            let descriptor = db.load_map(contract_id, "escrows").unwrap();
+            let key = |id: u128| {
+                Value::from(TupleData::from_data(vec![("id".into(), Value::UInt(id))]).unwrap())
+            };
+            let terms = TupleData::from_data(vec![
+                ("expiry".into(), Value::UInt(50)),
+                ("memo".into(), Value::buff_from(vec![1, 2, 3, 4]).unwrap()),
+            ])
+            .unwrap();
+            let expected = Value::from(
+                TupleData::from_data(vec![
+                    ("amount".into(), Value::UInt(100)),
+                    ("terms".into(), Value::from(terms)),
+                ])
+                .unwrap(),
+            );
+
+            let err = db
+                .remove_entry(contract_id, "escrows", &Value::UInt(1), &descriptor, &epoch)
+                .unwrap_err();
+            assert_eq!(
+                err,
+                CheckErrors::TypeValueError(descriptor.key_type.clone(), Value::UInt(1)).into()
+            );
+
+            assert_eq!(
+                db.remove_entry(contract_id, "escrows", &key(1), &descriptor, &epoch)
+                    .unwrap(),
+                Value::some(expected).unwrap()
+            );
+            assert_eq!(
+                db.fetch_entry(contract_id, "escrows", &key(1), &descriptor, &epoch)
+                    .unwrap(),
+                Value::none()
+            );
+            assert_eq!(
+                db.remove_entry(contract_id, "escrows", &key(1), &descriptor, &epoch)
+                    .unwrap(),
+                Value::none()
+            );
+            assert_eq!(
+                db.remove_entry(contract_id, "escrows", &key(2), &descriptor, &epoch)
+                    .unwrap(),
+                Value::none()
+            );
+        },
+    );
+}
+
+#[apply(test_epochs)]
+fn test_replace_entry(
+    epoch: StacksEpochId,
+    mut tl_env_factory: TopLevelMemoryEnvironmentGenerator,
+) {
+    with_kv_store_db(epoch, &mut tl_env_factory, |db, contract_id| {
+        let descriptor = db.load_map(contract_id, "kv-store").unwrap();
+
+        let err = db
+            .replace_entry(
+                contract_id,
+                "kv-store",
+                kv_key(1),
+                Value::Int(11),
+                &descriptor,
+                &epoch,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            CheckErrors::TypeValueError(descriptor.value_type.clone(), Value::Int(11)).into()
+        );
+
+        // fresh key
+        assert_eq!(
+            db.replace_entry(
+                contract_id,
+                "kv-store",
+                kv_key(3),
+                kv_value(30),
+                &descriptor,
+                &epoch
+            )
+            .unwrap(),
+            Value::none()
+        );
+        // overwritten key
+        assert_eq!(
+            db.replace_entry(
+                contract_id,
+                "kv-store",
+                kv_key(1),
+                kv_value(11),
+                &descriptor,
+                &epoch
+            )
+            .unwrap(),
+            Value::some(kv_value(10)).unwrap()
+        );
+        // key deleted by the contract
+        assert_eq!(
+            db.replace_entry(
+                contract_id,
+                "kv-store",
+                kv_key(2),
+                kv_value(21),
+                &descriptor,
+                &epoch
+            )
+            .unwrap(),
+            Value::none()
+        );
+
+        for (key, value) in [(1, 11), (2, 21), (3, 30)] {
+            assert_eq!(
+                db.fetch_entry(contract_id, "kv-store", &kv_key(key), &descriptor, &epoch)
+                    .unwrap(),
+                Value::some(kv_value(value)).unwrap()
+            );
+        }
+    });
+}
@@ -1707,6 +1707,29 @@ mod test {
         t.get("abcd").unwrap_err();
     }
 
+    #[test]
+    fn tuple_eq_ignores_field_order() {
+        let ab = TupleData::from_data(vec![
+            ("a".into(), Value::Int(1)),
+            ("b".into(), Value::Int(2)),
+        ])
+        .unwrap();
+        let ba = TupleData::from_data(vec![
+            ("b".into(), Value::Int(2)),
+            ("a".into(), Value::Int(1)),
+        ])
+        .unwrap();
+        assert_eq!(ab, ba);
+        assert_eq!(Value::from(ab.clone()), Value::from(ba));
+
+        let other = TupleData::from_data(vec![
+            ("a".into(), Value::Int(1)),
+            ("b".into(), Value::Int(3)),
+        ])
+        .unwrap();
+        assert_ne!(ab, other);
+    }
+
     #[test]
     fn test_some_displays() {
         assert_eq!(
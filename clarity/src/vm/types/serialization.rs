@@ -23,7 +23,7 @@ use std::{cmp, error, fmt, str};
 use serde_json::Value as JSONValue;
 use stacks_common::codec::{Error as codec_error, StacksMessageCodec};
 use stacks_common::types::StacksEpochId;
-use stacks_common::util::hash::{hex_bytes, to_hex};
+use stacks_common::util::hash::{hex_bytes, to_hex, Sha512Trunc256Sum};
 use stacks_common::util::retry::BoundReader;
 
 use super::{ListTypeData, TupleTypeSignature};
@@ -1223,6 +1223,13 @@ impl Value {
         Ok(to_hex(byte_serialization.as_slice()))
     }
 
+    /// SHA-512/256 digest of this value's consensus serialization. Values that compare
+    ///  as equal (e.g., tuples built with different field orders) have the same digest.
+    pub fn serialized_hash(&self) -> Result<Sha512Trunc256Sum, InterpreterError> {
+        let byte_serialization = self.serialize_to_vec()?;
+        Ok(Sha512Trunc256Sum::from_data(byte_serialization.as_slice()))
+    }
+
     /// Sanitize `value` against pre-2.4 serialization
     ///
     /// Returns Some if the sanitization is successful, or was not necessary.
@@ -2223,4 +2230,40 @@ pub mod tests {
         test_bad_expectation(contract_p2, TypeSignature::BoolType);
         test_bad_expectation(standard_p, TypeSignature::BoolType);
     }
+
+    #[test]
+    fn test_serialized_hash() {
+        assert_eq!(
+            Value::Int(1).serialized_hash().unwrap().to_hex(),
+            "5f748d81078532b78ac52d679eb4a2220a99c922948470df279ca2338186be30"
+        );
+        assert_eq!(
+            Value::Bool(true).serialized_hash().unwrap().to_hex(),
+            "05b577a248fe933b7c4cdaf4a50e9d61eb8cba9f86c4ad546ac25a0341128ed6"
+        );
+        assert_eq!(
+            Value::none().serialized_hash().unwrap().to_hex(),
+            "72fa3deefec6de0a951433c9ec22d4932321f11e734f60a8205d497e2528f6a0"
+        );
+
+        let ab = Value::from(
+            TupleData::from_data(vec![
+                ("a".into(), Value::Int(1)),
+                ("b".into(), Value::UInt(2)),
+            ])
+            .unwrap(),
+        );
+        let ba = Value::from(
+            TupleData::from_data(vec![
+                ("b".into(), Value::UInt(2)),
+                ("a".into(), Value::Int(1)),
+            ])
+            .unwrap(),
+        );
+        assert_eq!(
+            ab.serialized_hash().unwrap().to_hex(),
+            "fb422625ad080c62c52bd9a86cbf437380606276cc7200034c43ee60010fa984"
+        );
+        assert_eq!(ab.serialized_hash().unwrap(), ba.serialized_hash().unwrap());
+    }
 }
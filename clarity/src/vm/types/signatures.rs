@@ -547,6 +547,28 @@ impl TypeSignature {
         self.admits_type(epoch, &x_type)
     }
 
+    /// Check every value in `values` against this type, returning the index of the first
+    ///  value that is not admitted. A value whose `admits` check errors counts as not admitted.
+    /// Int, uint and bool types are checked with a single match on each value.
+    pub fn admits_slice(
+        &self,
+        epoch: &StacksEpochId,
+        values: &[Value],
+    ) -> std::result::Result<(), usize> {
+        let rejected = match self {
+            IntType => values.iter().position(|x| !matches!(x, Value::Int(_))),
+            UIntType => values.iter().position(|x| !matches!(x, Value::UInt(_))),
+            BoolType => values.iter().position(|x| !matches!(x, Value::Bool(_))),
+            _ => values
+                .iter()
+                .position(|x| !matches!(self.admits(epoch, x), Ok(true))),
+        };
+        match rejected {
+            Some(index) => Err(index),
+            None => Ok(()),
+        }
+    }
+
     pub fn admits_type(&self, epoch: &StacksEpochId, other: &TypeSignature) -> Result<bool> {
         match epoch {
             StacksEpochId::Epoch20 | StacksEpochId::Epoch2_05 => self.admits_type_v2_0(other),
@@ -2109,6 +2131,32 @@ mod test {
         }
     }
 
+    #[apply(test_clarity_versions)]
+    fn admits_slice(#[case] version: ClarityVersion, #[case] epoch: StacksEpochId) {
+        let int_list = TypeSignature::from_string("(list 2 int)", version, epoch);
+        let values = [
+            Value::Int(1),
+            Value::Int(2),
+            Value::UInt(3),
+            Value::Bool(true),
+        ];
+
+        assert_eq!(IntType.admits_slice(&epoch, &values[..2]), Ok(()));
+        assert_eq!(IntType.admits_slice(&epoch, &values), Err(2));
+        assert_eq!(UIntType.admits_slice(&epoch, &values), Err(0));
+        assert_eq!(BoolType.admits_slice(&epoch, &values[3..]), Ok(()));
+        assert_eq!(IntType.admits_slice(&epoch, &[]), Ok(()));
+
+        let lists = [
+            Value::cons_list_unsanitized(vec![Value::Int(1)]).unwrap(),
+            Value::cons_list_unsanitized(vec![Value::Int(1), Value::Int(2)]).unwrap(),
+            Value::cons_list_unsanitized(vec![Value::Int(1), Value::Int(2), Value::Int(3)])
+                .unwrap(),
+        ];
+        assert_eq!(int_list.admits_slice(&epoch, &lists[..2]), Ok(()));
+        assert_eq!(int_list.admits_slice(&epoch, &lists), Err(2));
+    }
+
     #[test]
     fn test_least_supertype() {
         let callables = [